@@ -2,7 +2,8 @@
 //!
 //! Provides detailed file and directory information:
 //! file size, line/word/character counts, MIME type guess,
-//! and directory entry listing with totals.
+//! and directory entry listing with totals (optionally recursive,
+//! with audit and dedupe scan modes).
 //!
 //! Zero external crates — uses only the Rust standard library.
 //!
@@ -20,10 +21,12 @@
 //! Test:
 //!   echo '{"type":"describe"}' | cargo run -q
 
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::fmt::Write as FmtWrite;
 use std::fs;
-use std::io::{self, BufRead, Write};
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufRead, Read as IoRead, Write};
 use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -64,7 +67,10 @@ enum JsonVal {
     Bool(bool),
     Null,
     Obj(HashMap<String, JsonVal>),
-    Arr(Vec<JsonVal>),
+    // No call parameter currently takes a JSON array, so the parsed
+    // elements are discarded — we only need to recognize and skip past
+    // array syntax while parsing an object's values.
+    Arr(()),
 }
 
 fn skip_ws(s: &[u8], pos: &mut usize) {
@@ -127,11 +133,10 @@ fn parse_val(s: &[u8], pos: &mut usize) -> Result<JsonVal, &'static str> {
         }
         Some(b'[') => {
             *pos += 1;
-            let mut arr = Vec::new();
             skip_ws(s, pos);
-            if s.get(*pos) == Some(&b']') { *pos += 1; return Ok(JsonVal::Arr(arr)); }
+            if s.get(*pos) == Some(&b']') { *pos += 1; return Ok(JsonVal::Arr(())); }
             loop {
-                arr.push(parse_val(s, pos)?);
+                parse_val(s, pos)?;
                 skip_ws(s, pos);
                 match s.get(*pos) {
                     Some(b',') => { *pos += 1; }
@@ -139,7 +144,7 @@ fn parse_val(s: &[u8], pos: &mut usize) -> Result<JsonVal, &'static str> {
                     _ => return Err("expected ',' or ']'"),
                 }
             }
-            Ok(JsonVal::Arr(arr))
+            Ok(JsonVal::Arr(()))
         }
         Some(b't') => { *pos += 4; Ok(JsonVal::Bool(true)) }
         Some(b'f') => { *pos += 5; Ok(JsonVal::Bool(false)) }
@@ -155,6 +160,35 @@ fn parse_val(s: &[u8], pos: &mut usize) -> Result<JsonVal, &'static str> {
     }
 }
 
+// ── JSON writer ───────────────────────────────────────────────────────────────
+// Mirrors the hand-rolled parser above: builds JSON text from ordered
+// key/value pairs rather than JsonVal, since JsonVal::Obj's HashMap can't
+// preserve field order.
+
+fn json_bool(b: bool) -> String {
+    if b { "true".to_string() } else { "false".to_string() }
+}
+
+fn json_num(n: f64) -> String {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        format!("{}", n as i64)
+    } else {
+        format!("{}", n)
+    }
+}
+
+fn json_obj(pairs: &[(&str, String)]) -> String {
+    let body: Vec<String> = pairs
+        .iter()
+        .map(|(k, v)| format!("{}:{}", json_str(k), v))
+        .collect();
+    format!("{{{}}}", body.join(","))
+}
+
+fn json_arr(items: &[String]) -> String {
+    format!("[{}]", items.join(","))
+}
+
 fn parse_obj(line: &str) -> Result<HashMap<String, JsonVal>, String> {
     let bytes = line.as_bytes();
     let mut pos = 0;
@@ -203,23 +237,141 @@ fn guess_mime(path: &Path) -> &'static str {
     }
 }
 
-fn info_file(path: &Path) -> Result<String, String> {
+// Magic-byte signatures checked in order; first match wins.
+const MAGIC_SIGNATURES: &[(&[u8], &str)] = &[
+    (&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A], "image/png"),
+    (&[0xFF, 0xD8, 0xFF], "image/jpeg"),
+    (b"GIF8", "image/gif"),
+    (b"%PDF", "application/pdf"),
+    (&[b'P', b'K', 0x03, 0x04], "application/zip"),
+    (&[0x1F, 0x8B], "application/gzip"),
+    (&[0x7F, b'E', b'L', b'F'], "application/x-elf"),
+];
+
+// Reads a bounded prefix of `path` and matches it against known magic-byte
+// signatures, falling back to a BOM check and a printable-text heuristic.
+fn sniff_mime(path: &Path) -> Option<&'static str> {
+    let mut file = fs::File::open(path).ok()?;
+    let mut buf = [0u8; 512];
+    let n = file.read(&mut buf).ok()?;
+    let buf = &buf[..n];
+
+    for (sig, mime) in MAGIC_SIGNATURES {
+        if buf.starts_with(sig) {
+            return Some(mime);
+        }
+    }
+
+    if buf.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        return Some("text/plain");
+    }
+    if buf.starts_with(&[0xFF, 0xFE]) || buf.starts_with(&[0xFE, 0xFF]) {
+        return Some("text/plain");
+    }
+
+    if buf.is_empty() {
+        return None;
+    }
+    let printable = buf
+        .iter()
+        .filter(|&&b| b == b'\n' || b == b'\r' || b == b'\t' || (0x20..0x7F).contains(&b))
+        .count();
+    if printable as f64 / buf.len() as f64 > 0.95 {
+        Some("text/plain")
+    } else {
+        Some("application/octet-stream")
+    }
+}
+
+// Decides whether an extension-guessed MIME type and a sniffed MIME type
+// genuinely disagree. The sniffer only ever reports a generic "text/plain"
+// for text content, so a specific text extension (text/x-rust,
+// application/json, …) paired with a sniffed "text/plain" is agreement,
+// not a mismatch — only a concrete signature clash, or text vs. binary,
+// counts as a conflict.
+fn mime_conflicts(ext_mime: &str, sniffed_mime: &str) -> bool {
+    if sniffed_mime == ext_mime {
+        return false;
+    }
+    let ext_is_textlike = ext_mime.starts_with("text/") || ext_mime == "application/json";
+    !(sniffed_mime == "text/plain" && ext_is_textlike)
+}
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+// Encodes `data` as base64 with standard padding. Implemented inline to
+// keep the zero-dependency guarantee.
+fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = *chunk.get(1).unwrap_or(&0);
+        let b2 = *chunk.get(2).unwrap_or(&0);
+
+        out.push(BASE64_ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(BASE64_ALPHABET[(((b0 & 0x03) << 4) | (b1 >> 4)) as usize] as char);
+        out.push(if chunk.len() > 1 {
+            BASE64_ALPHABET[(((b1 & 0x0F) << 2) | (b2 >> 6)) as usize] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            BASE64_ALPHABET[(b2 & 0x3F) as usize] as char
+        } else {
+            '='
+        });
+    }
+    out
+}
+
+// Reads up to `n` bytes from the start of `path`.
+fn read_head(path: &Path, n: usize) -> io::Result<Vec<u8>> {
+    let mut file = fs::File::open(path)?;
+    let mut buf = vec![0u8; n];
+    let read = file.read(&mut buf)?;
+    buf.truncate(read);
+    Ok(buf)
+}
+
+// Reads up to `n` bytes from the end of `path`.
+fn read_tail(path: &Path, n: usize) -> io::Result<Vec<u8>> {
+    use std::io::{Seek, SeekFrom};
+    let mut file = fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    let n = n.min(len as usize);
+    file.seek(SeekFrom::End(-(n as i64)))?;
+    let mut buf = vec![0u8; n];
+    file.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+fn info_file(path: &Path, preview_bytes: Option<usize>, preview_tail: bool) -> Result<String, String> {
     let meta = fs::metadata(path).map_err(|e| format!("cannot stat {}: {}", path.display(), e))?;
     let size = meta.len();
     let mime = guess_mime(path);
+    let sniffed = sniff_mime(path);
     let mtime = meta.modified().unwrap_or(UNIX_EPOCH);
 
     let mut out = String::new();
     writeln!(out, "path    : {}", path.display()).unwrap();
     writeln!(out, "type    : file").unwrap();
     writeln!(out, "size    : {} ({})", size, format_size(size)).unwrap();
-    writeln!(out, "mime    : {}", mime).unwrap();
+    writeln!(out, "mime    : {} (by extension)", mime).unwrap();
+    match sniffed {
+        Some(s) if mime_conflicts(mime, s) => {
+            writeln!(out, "sniffed : {} (content mismatch!)", s).unwrap();
+        }
+        Some(s) => writeln!(out, "sniffed : {}", s).unwrap(),
+        None => writeln!(out, "sniffed : (empty or unreadable)").unwrap(),
+    }
     writeln!(out, "modified: {}", format_mtime(mtime)).unwrap();
 
     // Count lines/words/chars for text files
+    let mut readable_as_text = false;
     if mime.starts_with("text/") || mime == "application/json" {
         match fs::read_to_string(path) {
             Ok(content) => {
+                readable_as_text = true;
                 let lines = content.lines().count();
                 let words = content.split_whitespace().count();
                 let chars = content.chars().count();
@@ -233,15 +385,49 @@ fn info_file(path: &Path) -> Result<String, String> {
         }
     }
 
+    if !readable_as_text {
+        if let Some(n) = preview_bytes {
+            if let Ok(head) = read_head(path, n) {
+                writeln!(out, "preview head ({} bytes, base64): {}", head.len(), base64_encode(&head)).unwrap();
+            }
+            if preview_tail {
+                if let Ok(tail) = read_tail(path, n) {
+                    writeln!(out, "preview tail ({} bytes, base64): {}", tail.len(), base64_encode(&tail)).unwrap();
+                }
+            }
+        }
+    }
+
     Ok(out.trim_end().to_string())
 }
 
-fn info_dir(path: &Path, max_entries: usize) -> Result<String, String> {
+// Matches `name` against a simple glob `pattern` supporting `*` (any run of
+// characters) and `?` (any single character). Implemented inline to keep
+// the zero-dependency guarantee.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pat: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = name.chars().collect();
+
+    fn matches(pat: &[char], text: &[char]) -> bool {
+        match pat.first() {
+            None => text.is_empty(),
+            Some('*') => {
+                matches(&pat[1..], text) || (!text.is_empty() && matches(pat, &text[1..]))
+            }
+            Some('?') => !text.is_empty() && matches(&pat[1..], &text[1..]),
+            Some(c) => text.first() == Some(c) && matches(&pat[1..], &text[1..]),
+        }
+    }
+
+    matches(&pat, &text)
+}
+
+fn info_dir(path: &Path, max_entries: usize, filter: Option<&str>, sort: Option<&str>) -> Result<String, String> {
     let meta = fs::metadata(path).map_err(|e| format!("cannot stat {}: {}", path.display(), e))?;
     let mtime = meta.modified().unwrap_or(UNIX_EPOCH);
 
     let entries = fs::read_dir(path).map_err(|e| format!("cannot read dir: {}", e))?;
-    let mut items: Vec<(String, bool, u64)> = Vec::new(); // (name, is_dir, size)
+    let mut items: Vec<(String, bool, u64, SystemTime)> = Vec::new(); // (name, is_dir, size, mtime)
     let mut total_size: u64 = 0;
 
     for entry in entries.flatten() {
@@ -249,24 +435,43 @@ fn info_dir(path: &Path, max_entries: usize) -> Result<String, String> {
         let meta = entry.metadata().ok();
         let is_dir = meta.as_ref().map(|m| m.is_dir()).unwrap_or(false);
         let size = meta.as_ref().map(|m| m.len()).unwrap_or(0);
+        let entry_mtime = meta.as_ref().and_then(|m| m.modified().ok()).unwrap_or(UNIX_EPOCH);
         total_size += size;
-        items.push((name, is_dir, size));
+        items.push((name, is_dir, size, entry_mtime));
+    }
+    let total_before_filter = items.len();
+
+    if let Some(pattern) = filter {
+        items.retain(|(name, _, _, _)| glob_match(pattern, name));
+        total_size = items.iter().map(|(_, _, size, _)| size).sum();
+    }
+
+    let (sort_field, desc) = match sort {
+        Some(s) if s.ends_with("_desc") => (s.trim_end_matches("_desc"), true),
+        Some(s) => (s, false),
+        None => ("", false),
+    };
+    match sort_field {
+        "name" => items.sort_by(|a, b| a.0.cmp(&b.0)),
+        "size" => items.sort_by_key(|item| item.2),
+        "mtime" => items.sort_by_key(|item| item.3),
+        // Default: dirs first, then alphabetical
+        _ => items.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0))),
+    }
+    if desc {
+        items.reverse();
     }
-    items.sort_by(|a, b| {
-        // Dirs first, then alphabetical
-        b.1.cmp(&a.1).then(a.0.cmp(&b.0))
-    });
 
     let mut out = String::new();
     writeln!(out, "path     : {}", path.display()).unwrap();
     writeln!(out, "type     : directory").unwrap();
-    writeln!(out, "entries  : {}", items.len()).unwrap();
+    writeln!(out, "entries  : {} (of {} before filter)", items.len(), total_before_filter).unwrap();
     writeln!(out, "total    : {}", format_size(total_size)).unwrap();
     writeln!(out, "modified : {}", format_mtime(mtime)).unwrap();
     writeln!(out).unwrap();
 
     let show = items.len().min(max_entries);
-    for (name, is_dir, size) in &items[..show] {
+    for (name, is_dir, size, _) in &items[..show] {
         let suffix = if *is_dir { "/" } else { "" };
         writeln!(out, "  {:42} {:>10}", format!("{}{}", name, suffix), format_size(*size)).unwrap();
     }
@@ -277,6 +482,334 @@ fn info_dir(path: &Path, max_entries: usize) -> Result<String, String> {
     Ok(out.trim_end().to_string())
 }
 
+fn info_file_json(path: &Path) -> Result<String, String> {
+    let meta = fs::metadata(path).map_err(|e| format!("cannot stat {}: {}", path.display(), e))?;
+    let size = meta.len();
+    let mime = guess_mime(path);
+    let sniffed = sniff_mime(path);
+    let mtime = meta.modified().unwrap_or(UNIX_EPOCH);
+    let mtime_epoch = mtime.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+
+    let mut fields: Vec<(&str, String)> = vec![
+        ("path", json_str(&path.display().to_string())),
+        ("type", json_str("file")),
+        ("size_bytes", json_num(size as f64)),
+        ("mime", json_str(mime)),
+        ("mime_sniffed", sniffed.map(json_str).unwrap_or_else(|| "null".to_string())),
+        ("mtime_epoch", json_num(mtime_epoch as f64)),
+    ];
+
+    if mime.starts_with("text/") || mime == "application/json" {
+        if let Ok(content) = fs::read_to_string(path) {
+            fields.push(("lines", json_num(content.lines().count() as f64)));
+            fields.push(("words", json_num(content.split_whitespace().count() as f64)));
+            fields.push(("chars", json_num(content.chars().count() as f64)));
+        }
+    }
+
+    Ok(json_obj(&fields))
+}
+
+fn info_dir_json(path: &Path, max_entries: usize) -> Result<String, String> {
+    let meta = fs::metadata(path).map_err(|e| format!("cannot stat {}: {}", path.display(), e))?;
+    let mtime = meta.modified().unwrap_or(UNIX_EPOCH);
+    let mtime_epoch = mtime.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+
+    let entries = fs::read_dir(path).map_err(|e| format!("cannot read dir: {}", e))?;
+    let mut items: Vec<(String, bool, u64)> = Vec::new(); // (name, is_dir, size)
+    let mut total_size: u64 = 0;
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let meta = entry.metadata().ok();
+        let is_dir = meta.as_ref().map(|m| m.is_dir()).unwrap_or(false);
+        let size = meta.as_ref().map(|m| m.len()).unwrap_or(0);
+        total_size += size;
+        items.push((name, is_dir, size));
+    }
+    items.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let truncated = items.len() > max_entries;
+    let show = items.len().min(max_entries);
+    let entry_objs: Vec<String> = items[..show]
+        .iter()
+        .map(|(name, is_dir, size)| {
+            json_obj(&[
+                ("name", json_str(name)),
+                ("is_dir", json_bool(*is_dir)),
+                ("size", json_num(*size as f64)),
+            ])
+        })
+        .collect();
+
+    let fields = [
+        ("path", json_str(&path.display().to_string())),
+        ("type", json_str("directory")),
+        ("total_size_bytes", json_num(total_size as f64)),
+        ("entry_count", json_num(items.len() as f64)),
+        ("truncated", json_bool(truncated)),
+        ("mtime_epoch", json_num(mtime_epoch as f64)),
+        ("entries", json_arr(&entry_objs)),
+    ];
+
+    Ok(json_obj(&fields))
+}
+
+#[derive(Default)]
+struct DirStats {
+    total_size: u64,
+    file_count: u64,
+    dir_count: u64,
+    by_ext: HashMap<String, (u64, u64)>, // ext -> (file count, total size)
+}
+
+// Walks `path` accumulating aggregate totals, bounded by `max_depth`
+// (0 = only the entries directly inside `path`). Symlinked directories are
+// followed but guarded against cycles by recording canonical paths already
+// visited.
+fn collect_dir_stats(path: &Path, max_depth: usize, depth: usize, visited: &mut std::collections::HashSet<std::path::PathBuf>, stats: &mut DirStats) {
+    let entries = match fs::read_dir(path) {
+        Ok(e) => e,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let entry_path = entry.path();
+        let meta = match fs::metadata(&entry_path) {
+            Ok(m) => m,
+            Err(_) => continue,
+        };
+        if meta.is_dir() {
+            let canonical = fs::canonicalize(&entry_path).unwrap_or_else(|_| entry_path.clone());
+            if !visited.insert(canonical) {
+                continue; // already visited — symlink cycle
+            }
+            stats.dir_count += 1;
+            if depth < max_depth {
+                collect_dir_stats(&entry_path, max_depth, depth + 1, visited, stats);
+            }
+        } else if meta.is_file() {
+            stats.file_count += 1;
+            stats.total_size += meta.len();
+            let ext = entry_path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| format!(".{}", e))
+                .unwrap_or_else(|| "(no ext)".to_string());
+            let entry = stats.by_ext.entry(ext).or_insert((0, 0));
+            entry.0 += 1;
+            entry.1 += meta.len();
+        }
+    }
+}
+
+fn recursive_info_dir(path: &Path, max_depth: usize) -> Result<String, String> {
+    let meta = fs::metadata(path).map_err(|e| format!("cannot stat {}: {}", path.display(), e))?;
+    let mtime = meta.modified().unwrap_or(UNIX_EPOCH);
+
+    let mut stats = DirStats::default();
+    let mut visited = std::collections::HashSet::new();
+    if let Ok(canonical) = fs::canonicalize(path) {
+        visited.insert(canonical);
+    }
+    collect_dir_stats(path, max_depth, 0, &mut visited, &mut stats);
+
+    let mut out = String::new();
+    writeln!(out, "path     : {}", path.display()).unwrap();
+    writeln!(out, "type     : directory (recursive, max_depth={})", max_depth).unwrap();
+    writeln!(out, "files    : {}", stats.file_count).unwrap();
+    writeln!(out, "dirs     : {}", stats.dir_count).unwrap();
+    writeln!(out, "total    : {}", format_size(stats.total_size)).unwrap();
+    writeln!(out, "modified : {}", format_mtime(mtime)).unwrap();
+    writeln!(out).unwrap();
+
+    let mut by_ext: Vec<(String, u64, u64)> = stats
+        .by_ext
+        .into_iter()
+        .map(|(ext, (count, size))| (ext, count, size))
+        .collect();
+    by_ext.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(&b.0)));
+
+    writeln!(out, "by extension:").unwrap();
+    for (ext, count, size) in &by_ext {
+        writeln!(out, "  {:10} {:6} files  {:>10}", ext, count, format_size(*size)).unwrap();
+    }
+
+    Ok(out.trim_end().to_string())
+}
+
+// Recursively collects regular files under `root`, bounded by `max_depth`
+// (0 = files directly inside `root`). Symlinks are not followed.
+fn walk_files(root: &Path, max_depth: usize, depth: usize, out: &mut Vec<std::path::PathBuf>) {
+    let entries = match fs::read_dir(root) {
+        Ok(e) => e,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let file_type = match entry.file_type() {
+            Ok(t) => t,
+            Err(_) => continue,
+        };
+        if file_type.is_symlink() {
+            continue;
+        } else if file_type.is_dir() {
+            if depth < max_depth {
+                walk_files(&path, max_depth, depth + 1, out);
+            }
+        } else if file_type.is_file() {
+            out.push(path);
+        }
+    }
+}
+
+// Extension guessed from a sniffed MIME type, used to suggest a correction
+// when an audited file's extension disagrees with its content.
+fn mime_to_ext(mime: &str) -> Option<&'static str> {
+    match mime {
+        "image/png" => Some("png"),
+        "image/jpeg" => Some("jpg"),
+        "image/gif" => Some("gif"),
+        "application/pdf" => Some("pdf"),
+        "application/zip" => Some("zip"),
+        "application/gzip" => Some("gz"),
+        "application/x-elf" => Some("elf"),
+        "text/plain" => Some("txt"),
+        _ => None,
+    }
+}
+
+fn audit_dir(path: &Path, max_depth: usize) -> Result<String, String> {
+    let mut files = Vec::new();
+    walk_files(path, max_depth, 0, &mut files);
+    files.sort();
+
+    let mut mismatches = Vec::new();
+    for file in &files {
+        let ext_mime = guess_mime(file);
+        let sniffed = match sniff_mime(file) {
+            Some(m) => m,
+            None => continue,
+        };
+        if mime_conflicts(ext_mime, sniffed) {
+            mismatches.push((file.clone(), ext_mime, sniffed));
+        }
+    }
+
+    let mut out = String::new();
+    writeln!(out, "path      : {}", path.display()).unwrap();
+    writeln!(out, "mode      : audit").unwrap();
+    writeln!(out, "scanned   : {} files", files.len()).unwrap();
+    writeln!(out, "mismatches: {}", mismatches.len()).unwrap();
+    writeln!(out).unwrap();
+
+    for (file, ext_mime, sniffed) in &mismatches {
+        writeln!(out, "  {}", file.display()).unwrap();
+        writeln!(out, "    extension says: {}", ext_mime).unwrap();
+        writeln!(out, "    content is    : {}", sniffed).unwrap();
+        if let Some(suggested) = mime_to_ext(sniffed) {
+            writeln!(out, "    suggested ext : .{}", suggested).unwrap();
+        }
+    }
+
+    Ok(out.trim_end().to_string())
+}
+
+const PARTIAL_HASH_BYTES: usize = 4096;
+
+// Hashes the first `PARTIAL_HASH_BYTES` of `path` with SipHash (DefaultHasher),
+// used as a cheap pre-filter before a full-file comparison.
+fn partial_hash(path: &Path) -> Option<u64> {
+    let mut file = fs::File::open(path).ok()?;
+    let mut buf = [0u8; PARTIAL_HASH_BYTES];
+    let n = file.read(&mut buf).ok()?;
+    let mut hasher = DefaultHasher::new();
+    buf[..n].hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+// Hashes the full contents of `path` with SipHash (DefaultHasher), used to
+// confirm equality after a size and partial-hash collision.
+fn full_hash(path: &Path) -> Option<u64> {
+    let mut file = fs::File::open(path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    let mut buf = [0u8; 65536];
+    loop {
+        let n = file.read(&mut buf).ok()?;
+        if n == 0 {
+            break;
+        }
+        buf[..n].hash(&mut hasher);
+    }
+    Some(hasher.finish())
+}
+
+fn dedupe_dir(path: &Path, max_depth: usize, min_size: u64) -> Result<String, String> {
+    let mut files = Vec::new();
+    walk_files(path, max_depth, 0, &mut files);
+
+    // Phase 1: bucket by exact size, discarding sizes with a single file.
+    let mut by_size: HashMap<u64, Vec<std::path::PathBuf>> = HashMap::new();
+    for file in files {
+        if let Ok(meta) = fs::metadata(&file) {
+            let size = meta.len();
+            if size >= min_size {
+                by_size.entry(size).or_default().push(file);
+            }
+        }
+    }
+    by_size.retain(|_, v| v.len() > 1);
+
+    let mut duplicate_sets: Vec<(u64, Vec<std::path::PathBuf>)> = Vec::new();
+    let mut reclaimable: u64 = 0;
+
+    for (size, candidates) in by_size {
+        // Phase 2: sub-group by partial hash (first block only).
+        let mut by_partial: HashMap<u64, Vec<std::path::PathBuf>> = HashMap::new();
+        for file in candidates {
+            if let Some(h) = partial_hash(&file) {
+                by_partial.entry(h).or_default().push(file);
+            }
+        }
+
+        for (_, group) in by_partial {
+            if group.len() < 2 {
+                continue;
+            }
+            // Phase 3: confirm with a full-file hash.
+            let mut by_full: HashMap<u64, Vec<std::path::PathBuf>> = HashMap::new();
+            for file in group {
+                if let Some(h) = full_hash(&file) {
+                    by_full.entry(h).or_default().push(file);
+                }
+            }
+            for (_, mut set) in by_full {
+                if set.len() > 1 {
+                    set.sort();
+                    reclaimable += size * (set.len() as u64 - 1);
+                    duplicate_sets.push((size, set));
+                }
+            }
+        }
+    }
+    duplicate_sets.sort_by_key(|(size, _)| std::cmp::Reverse(*size));
+
+    let mut out = String::new();
+    writeln!(out, "path       : {}", path.display()).unwrap();
+    writeln!(out, "mode       : dedupe").unwrap();
+    writeln!(out, "dup sets   : {}", duplicate_sets.len()).unwrap();
+    writeln!(out, "reclaimable: {} ({})", reclaimable, format_size(reclaimable)).unwrap();
+    writeln!(out).unwrap();
+
+    for (size, set) in &duplicate_sets {
+        writeln!(out, "  {} × {} ({} each)", set.len(), format_size(*size), size).unwrap();
+        for file in set {
+            writeln!(out, "    {}", file.display()).unwrap();
+        }
+    }
+
+    Ok(out.trim_end().to_string())
+}
+
 fn handle_call(params: &HashMap<String, JsonVal>) -> (String, bool) {
     let path_str = match get_str(params, "path") {
         Some(p) => p,
@@ -284,19 +817,43 @@ fn handle_call(params: &HashMap<String, JsonVal>) -> (String, bool) {
     };
 
     let max_entries = match params.get("max_entries") {
-        Some(JsonVal::Num(n)) => (*n as usize).max(1).min(500),
+        Some(JsonVal::Num(n)) => (*n as usize).clamp(1, 500),
         _ => 50,
     };
+    let max_depth = match params.get("max_depth") {
+        Some(JsonVal::Num(n)) => (*n as usize).clamp(1, 64),
+        _ => 16,
+    };
+    let min_size = match params.get("min_size") {
+        Some(JsonVal::Num(n)) => *n as u64,
+        _ => 0,
+    };
+    let mode = get_str(params, "mode");
+    let recursive = matches!(params.get("recursive"), Some(JsonVal::Bool(true)));
+    let json_format = matches!(get_str(params, "format"), Some("json"));
+    let filter = get_str(params, "filter");
+    let sort = get_str(params, "sort");
+    let preview_bytes = match params.get("preview_bytes") {
+        Some(JsonVal::Num(n)) => Some((*n as usize).clamp(1, 65536)),
+        _ => None,
+    };
+    let preview_tail = matches!(params.get("preview_tail"), Some(JsonVal::Bool(true)));
 
     let path = Path::new(path_str);
     if !path.exists() {
         return (format!("Error: path not found: {}", path_str), true);
     }
 
-    let result = if path.is_dir() {
-        info_dir(path, max_entries)
-    } else {
-        info_file(path)
+    let result = match mode {
+        Some("audit") if path.is_dir() => audit_dir(path, max_depth),
+        Some("audit") => Err(format!("'mode':'audit' requires a directory path, got: {}", path_str)),
+        Some("dedupe") if path.is_dir() => dedupe_dir(path, max_depth, min_size),
+        Some("dedupe") => Err(format!("'mode':'dedupe' requires a directory path, got: {}", path_str)),
+        _ if path.is_dir() && recursive => recursive_info_dir(path, max_depth),
+        _ if path.is_dir() && json_format => info_dir_json(path, max_entries),
+        _ if path.is_dir() => info_dir(path, max_entries, filter, sort),
+        _ if json_format => info_file_json(path),
+        _ => info_file(path, preview_bytes, preview_tail),
     };
 
     match result {
@@ -307,7 +864,7 @@ fn handle_call(params: &HashMap<String, JsonVal>) -> (String, bool) {
 
 // ── Main ──────────────────────────────────────────────────────────────────────
 
-const DEFINITION: &str = r#"{"name":"file_info","description":"Get detailed metadata and statistics about a file or directory. For files: size, MIME type, line/word/character count. For directories: entry listing with sizes. Useful for understanding the contents of a path before reading it.","parameters":{"type":"object","properties":{"path":{"type":"string","description":"File or directory path to inspect"},"max_entries":{"type":"integer","description":"Maximum directory entries to list (default: 50, max: 500)"}},"required":["path"]}}"#;
+const DEFINITION: &str = r#"{"name":"file_info","description":"Get detailed metadata and statistics about a file or directory. For files: size, MIME type, line/word/character count. For directories: entry listing with sizes, an audit of extension/content MIME mismatches, or a duplicate-file report. Useful for understanding the contents of a path before reading it.","parameters":{"type":"object","properties":{"path":{"type":"string","description":"File or directory path to inspect"},"max_entries":{"type":"integer","description":"Maximum directory entries to list (default: 50, max: 500)"},"mode":{"type":"string","description":"Directory scan mode: omit for a plain listing, 'audit' to report files whose extension disagrees with their sniffed content type, or 'dedupe' to report groups of identical files"},"max_depth":{"type":"integer","description":"Maximum recursion depth for 'audit'/'dedupe' modes and for 'recursive' listings (default: 16, max: 64)"},"min_size":{"type":"integer","description":"Skip files smaller than this many bytes in 'dedupe' mode (default: 0)"},"recursive":{"type":"boolean","description":"Walk the whole subtree and report aggregate totals plus a per-extension breakdown, instead of listing one level"},"format":{"type":"string","description":"Output format for plain file/directory info: 'text' (default) for a human-formatted blob, or 'json' for a machine-readable object"},"filter":{"type":"string","description":"Glob pattern (supports * and ?) matched against entry names in a plain directory listing; not applied with format 'json'"},"sort":{"type":"string","description":"Sort plain directory listings by 'name', 'size', or 'mtime'; append '_desc' (e.g. 'size_desc') to reverse; not applied with format 'json'"},"preview_bytes":{"type":"integer","description":"For binary files, include a base64-encoded preview of this many bytes from the start (max 65536); not included with format 'json'"},"preview_tail":{"type":"boolean","description":"With preview_bytes set, also include a base64 preview of the same number of bytes from the end of the file"}},"required":["path"]}}"#;
 
 fn main() {
     let stdin = io::stdin();